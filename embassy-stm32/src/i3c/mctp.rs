@@ -0,0 +1,135 @@
+//! MCTP-over-I3C transport binding (DMTF DSP0233), layered on top of
+//! [`I3cTarget`](super::I3cTarget) rather than baked into the generic driver.
+//!
+//! Messages larger than a single I3C private write/read are split across
+//! multiple packets using the MCTP transport header's SOM/EOM/packet-sequence
+//! fields, and a pending read is signalled to the controller with an IBI as
+//! described by `getcapr.cappend`.
+
+use super::{I3cError, I3cTarget, IbiError, Instance};
+
+/// Maximum MCTP packet payload this binding will assemble/accept, excluding
+/// the 1-byte transport header.
+pub const MCTP_BASELINE_MTU: usize = 64;
+
+/// Errors from the MCTP transport layer.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum MctpError {
+    /// The underlying I3C transfer failed.
+    I3c(I3cError),
+    /// Raising the pending-read IBI failed.
+    Ibi(IbiError),
+    /// The assembled message does not fit in the caller's buffer.
+    BufferTooSmall,
+    /// A packet arrived with an unexpected sequence number, SOM, or EOM.
+    Framing,
+}
+
+impl From<I3cError> for MctpError {
+    fn from(err: I3cError) -> Self {
+        MctpError::I3c(err)
+    }
+}
+
+impl From<IbiError> for MctpError {
+    fn from(err: IbiError) -> Self {
+        MctpError::Ibi(err)
+    }
+}
+
+/// MCTP transport header control byte: SOM (bit 7), EOM (bit 6), 2-bit packet
+/// sequence number (bits 5:4), tag-owner and message-tag bits are left zeroed
+/// since this binding only needs one outstanding exchange at a time.
+fn control_byte(som: bool, eom: bool, seq: u8) -> u8 {
+    ((som as u8) << 7) | ((eom as u8) << 6) | ((seq & 0b11) << 4)
+}
+
+/// Send `msg` as one or more MCTP packets over I3C private writes.
+pub async fn send_mctp<'d, T: Instance, TXDMA, RXDMA>(
+    target: &mut I3cTarget<'d, T, TXDMA, RXDMA>,
+    msg: &[u8],
+) -> Result<(), MctpError>
+where
+    TXDMA: super::TxDma<T>,
+{
+    let mut seq: u8 = 0;
+    let mut chunks = msg.chunks(MCTP_BASELINE_MTU).peekable();
+    // An empty message is still one (empty) packet carrying SOM and EOM.
+    if chunks.peek().is_none() {
+        target.write(&[control_byte(true, true, 0)]).await?;
+        return Ok(());
+    }
+
+    let mut first = true;
+    let mut packet = [0u8; 1 + MCTP_BASELINE_MTU];
+    while let Some(chunk) = chunks.next() {
+        let last = chunks.peek().is_none();
+        packet[0] = control_byte(first, last, seq);
+        packet[1..=chunk.len()].copy_from_slice(chunk);
+        target.write(&packet[..=chunk.len()]).await?;
+
+        first = false;
+        seq = (seq + 1) & 0b11;
+    }
+
+    Ok(())
+}
+
+/// Receive an MCTP message into `buf`, reassembling packets until EOM.
+///
+/// Returns the number of bytes written to `buf`.
+pub async fn recv_mctp<'d, T: Instance, TXDMA, RXDMA>(
+    target: &mut I3cTarget<'d, T, TXDMA, RXDMA>,
+    buf: &mut [u8],
+) -> Result<usize, MctpError>
+where
+    RXDMA: super::RxDma<T>,
+{
+    let mut expected_seq: u8 = 0;
+    let mut written = 0;
+    let mut packet = [0u8; 1 + MCTP_BASELINE_MTU];
+
+    loop {
+        let n = target.read(&mut packet).await?;
+        if n == 0 {
+            return Err(MctpError::Framing);
+        }
+        let header = packet[0];
+        let som = header & 0x80 != 0;
+        let eom = header & 0x40 != 0;
+        let seq = (header >> 4) & 0b11;
+
+        if som && written != 0 {
+            // Controller restarted a message before ending the previous one.
+            return Err(MctpError::Framing);
+        }
+        if !som && seq != expected_seq {
+            return Err(MctpError::Framing);
+        }
+
+        let payload = &packet[1..n];
+        let dst = buf.get_mut(written..written + payload.len()).ok_or(MctpError::BufferTooSmall)?;
+        dst.copy_from_slice(payload);
+        written += payload.len();
+        expected_seq = (seq + 1) & 0b11;
+
+        if eom {
+            return Ok(written);
+        }
+    }
+}
+
+/// Notify the controller that a response is ready to be read, using the
+/// pending-read capability `getcapr.cappend` advertises.
+///
+/// Returns once the controller has accepted the IBI; it does not wait for
+/// the private read itself, whose data is sent with [`send_mctp`].
+pub async fn notify_pending_read<'d, T: Instance, TXDMA, RXDMA>(
+    target: &mut I3cTarget<'d, T, TXDMA, RXDMA>,
+) -> Result<(), MctpError> {
+    // The pending-read mandatory byte carries no MCTP-specific payload; the
+    // controller is expected to follow up with a private read.
+    target.request_ibi(0x00, &[]).await?;
+    Ok(())
+}
@@ -1,7 +1,11 @@
 #![macro_use]
 
+#[cfg(feature = "mctp")]
+pub mod mctp;
+
 use core::marker::PhantomData;
 
+use embassy_futures::select::{select, Either};
 use embassy_hal_common::{into_ref, PeripheralRef};
 use embassy_sync::waitqueue::AtomicWaker;
 
@@ -50,6 +54,60 @@ foreach_interrupt!(
     };
 );
 
+/// I3C target-mode errors.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum I3cError {
+    /// Controller NACKed the transfer.
+    Nack,
+    /// Framing error on the bus.
+    Framing,
+    /// RX FIFO overrun / TX FIFO underrun.
+    Overrun,
+    /// Transfer was aborted by the controller.
+    Aborted,
+}
+
+/// Errors raised while requesting an In-Band Interrupt.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum IbiError {
+    /// The controller rejected (NACKed) the IBI.
+    Rejected,
+    /// The mandatory byte plus `payload` is longer than the IBI payload size
+    /// programmed via `TargetConfig::ibi_payload_size` (`maxrlr().ibip()`).
+    PayloadTooLarge,
+    /// A bus error other than an IBI NACK arrived while the request was
+    /// pending.
+    Bus(I3cError),
+}
+
+/// A Common Command Code (or related event) observed from the controller,
+/// decoded from `evr()`/`sr()` in the ISR and surfaced through
+/// [`I3cTarget::next_event`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum I3cTargetEvent {
+    /// SETDASA/SETNEWDA/RSTDAA: the controller (re)assigned our dynamic address.
+    DynamicAddressChange(u8),
+    /// SETMRL: the controller changed the maximum read length.
+    MaxReadLength(u16),
+    /// SETMWL: the controller changed the maximum write length.
+    MaxWriteLength(u16),
+    /// GETMXDS: the controller asked for our max data speed / turnaround time.
+    GetMaxDataSpeed,
+    /// ENEC: the controller enabled one or more of our optional events (bitmask).
+    EventsEnabled(u8),
+    /// DISEC: the controller disabled one or more of our optional events (bitmask).
+    EventsDisabled(u8),
+    /// The controller acknowledged our hot-join request.
+    HotJoinAcked,
+    /// The bus entered an HDR mode.
+    HdrModeEntered,
+    /// The bus returned to SDR mode.
+    HdrModeExited,
+}
+
 /// Interrupt handler.
 pub struct InterruptHandler<T: Instance> {
     _phantom: PhantomData<T>,
@@ -57,35 +115,180 @@ pub struct InterruptHandler<T: Instance> {
 
 impl<T: Instance> interrupt::typelevel::Handler<T::Interrupt> for InterruptHandler<T> {
     unsafe fn on_interrupt() {
-        info!("int, ev {:08x}",
-            T::regs().evr().read().0,
-            );
-        // let regs = T::regs();
-        // let sr = regs.sr().read();
+        let regs = T::regs();
+        let evr = regs.evr().read();
+        let sr = regs.sr().read();
+        let ser = regs.ser().read();
+
+        info!("int, ev {:08x} sr {:08x} ser {:08x}", evr.0, sr.0, ser.0);
+
+        let ccc_event = sr.tcr()
+            || sr.tc()
+            || sr.rxfnf()
+            || sr.txfnf()
+            || sr.ibif()
+            || sr.enecf()
+            || sr.disecf()
+            || sr.hdrmf()
+            || sr.hdrxf()
+            || evr.daif()
+            || evr.daupdf()
+            || evr.mwlupdf()
+            || evr.mrlupdf()
+            || evr.getaccr()
+            || evr.hjf();
+        if ccc_event || ser.ibinakf() {
+            T::state().waker.wake();
+        }
+
+        if ser.0 != 0 {
+            let err = if ser.nakf() || ser.ibinakf() {
+                // A rejected IBI is, from the target's perspective, the
+                // controller NACKing what it sent - reuse `I3cError::Nack`
+                // so `request_ibi` can tell a real rejection apart from an
+                // unrelated bus fault via the same mailbox everything else
+                // goes through.
+                I3cError::Nack
+            } else if ser.fef() {
+                I3cError::Framing
+            } else if ser.orunf() || ser.urunf() {
+                I3cError::Overrun
+            } else {
+                I3cError::Aborted
+            };
+            T::state().error.store(err);
+            T::state().waker.wake();
+            // ser, like every other status register here, is write-1-to-clear;
+            // leaving it set would make this same error re-fire (and get
+            // re-stored) on the next unrelated interrupt.
+            regs.ser().write(|w| w.0 = ser.0);
+        }
 
-        // if sr.tcr() || sr.tc() {
-        //     T::state().waker.wake();
-        // }
-        // // The flag can only be cleared by writting to nbytes, we won't do that here, so disable
-        // // the interrupt
-        // critical_section::with(|_| {
-        //     regs.cr1().modify(|w| w.set_tcie(false));
-        // });
+        // Every flag here is level-triggered and stays set until the driver
+        // explicitly clears it (or, for RX/TX FIFO flags, until the FIFO
+        // state changes) once it has actually observed the event. Disable
+        // the interrupt-enable bit for every source that just fired so the
+        // ISR can't re-enter on the same still-pending flag; the driver
+        // re-enables the bit it cares about right before it next awaits.
+        critical_section::with(|_| {
+            regs.ier().modify(|w| {
+                if ser.0 != 0 {
+                    w.set_errie(false);
+                }
+                if sr.rxfnf() {
+                    w.set_rxfnfie(false);
+                }
+                if sr.txfnf() {
+                    w.set_txfnfie(false);
+                }
+                if sr.ibif() {
+                    w.set_ibiie(false);
+                }
+                if sr.enecf() {
+                    w.set_enecie(false);
+                }
+                if sr.disecf() {
+                    w.set_disecie(false);
+                }
+                if sr.hdrmf() {
+                    w.set_hdrmie(false);
+                }
+                if sr.hdrxf() {
+                    w.set_hdrxie(false);
+                }
+                if evr.daif() {
+                    w.set_daie(false);
+                }
+                if evr.daupdf() {
+                    w.set_daupdie(false);
+                }
+                if evr.mwlupdf() {
+                    w.set_mwlupdie(false);
+                }
+                if evr.mrlupdf() {
+                    w.set_mrlupdie(false);
+                }
+                if evr.getaccr() {
+                    w.set_getaccrie(false);
+                }
+                if evr.hjf() {
+                    w.set_hjie(false);
+                }
+            });
+        });
     }
 }
 
 pub struct State {
     waker: AtomicWaker,
+    error: StateError,
 }
 
 impl State {
     pub(crate) const fn new() -> Self {
         Self {
             waker: AtomicWaker::new(),
+            error: StateError::new(),
         }
     }
 }
 
+/// Single-slot mailbox for the most recent error reported by the ISR.
+struct StateError {
+    inner: critical_section::Mutex<core::cell::Cell<Option<I3cError>>>,
+}
+
+impl StateError {
+    const fn new() -> Self {
+        Self {
+            inner: critical_section::Mutex::new(core::cell::Cell::new(None)),
+        }
+    }
+
+    fn store(&self, err: I3cError) {
+        critical_section::with(|cs| self.inner.borrow(cs).set(Some(err)));
+    }
+
+    fn take(&self) -> Option<I3cError> {
+        critical_section::with(|cs| self.inner.borrow(cs).take())
+    }
+}
+
+
+/// Wait for the ISR to report a bus error, clearing it once observed.
+///
+/// Shared by [`I3cTarget`] and [`I3cController`]'s `read`/`write`, which race
+/// this against their DMA transfer since a controller-side NACK or other bus
+/// error never completes the DMA on its own.
+async fn wait_for_error<T: Instance>() -> I3cError {
+    core::future::poll_fn(|cx| {
+        T::state().waker.register(cx.waker());
+        match T::state().error.take() {
+            Some(err) => core::task::Poll::Ready(err),
+            None => core::task::Poll::Pending,
+        }
+    })
+    .await
+}
+
+/// Enable the CCC-related interrupt sources backing every [`I3cTargetEvent`]
+/// variant.
+///
+/// Shared between [`I3cTarget::new`] and [`I3cTarget::next_event`]'s
+/// re-arm loop so the two can't drift apart.
+fn arm_ccc_interrupts(regs: crate::pac::i3c::I3c) {
+    regs.ier().modify(|reg| {
+        reg.set_daupdie(true);
+        reg.set_mrlupdie(true);
+        reg.set_mwlupdie(true);
+        reg.set_getaccrie(true);
+        reg.set_hjie(true);
+        reg.set_enecie(true);
+        reg.set_disecie(true);
+        reg.set_hdrmie(true);
+        reg.set_hdrxie(true);
+    });
+}
 
 pub struct I3cTarget<'d, T: Instance, TXDMA = NoDma, RXDMA = NoDma> {
     _peri: PeripheralRef<'d, T>,
@@ -94,12 +297,50 @@ pub struct I3cTarget<'d, T: Instance, TXDMA = NoDma, RXDMA = NoDma> {
     rx_dma: PeripheralRef<'d, RXDMA>,
 }
 
-#[derive(Default)]
+/// Decode the `maxrlr().ibip()` field into a maximum IBI data byte count
+/// (mandatory byte included). The field is not a plain byte count: 0b000
+/// means no IBI data at all, 0b001..=0b100 give 1..=4 bytes directly, and
+/// 0b101..=0b111 double from there (8/16/32 bytes).
+const fn ibip_to_bytes(ibip: u8) -> u8 {
+    match ibip {
+        0 => 0,
+        1..=4 => ibip,
+        5 => 8,
+        6 => 16,
+        _ => 32,
+    }
+}
+
 pub struct TargetConfig {
     // Device Characteristics Register
     dcr: u8,
     // 4 bit MIPI Instance ID, as part of the PID
     instance_id: Option<u8>,
+    // IBI mandatory/payload data maximum size, in maxrlr.ibip encoding
+    // (see `ibip_to_bytes` for the encoding -> byte count mapping)
+    pub ibi_payload_size: u8,
+    // Maximum read length advertised in maxrlr.mrl
+    pub max_read_length: u16,
+    // Maximum write length advertised in maxwlr.mwl
+    pub max_write_length: u16,
+    // bcr.bcr2: advertise that our IBIs carry a mandatory data byte
+    pub ibi_has_mandatory_byte: bool,
+    // getcapr.cappend: advertise pending-read notification capability
+    pub pending_read_capable: bool,
+}
+
+impl Default for TargetConfig {
+    fn default() -> Self {
+        Self {
+            dcr: 0,
+            instance_id: None,
+            ibi_payload_size: 0b001,
+            max_read_length: 100,
+            max_write_length: 100,
+            ibi_has_mandatory_byte: true,
+            pending_read_capable: true,
+        }
+    }
 }
 
 impl<'d, T: Instance, TXDMA, RXDMA> I3cTarget<'d, T, TXDMA, RXDMA> {
@@ -146,16 +387,13 @@ impl<'d, T: Instance, TXDMA, RXDMA> I3cTarget<'d, T, TXDMA, RXDMA> {
         });
 
         T::regs().devr0().modify(|reg| {
-            // TODO MCTP specific?
             reg.set_ibien(true);
             reg.set_cren(true);
             reg.set_hjen(true);
         });
 
         T::regs().bcr().modify(|reg| {
-            // ibi has mandatory data byte payload
-            // TODO MCTP specific?
-            reg.set_bcr2(true);
+            reg.set_bcr2(config.ibi_has_mandatory_byte);
         });
 
         T::regs().dcr().modify(|reg| {
@@ -163,22 +401,16 @@ impl<'d, T: Instance, TXDMA, RXDMA> I3cTarget<'d, T, TXDMA, RXDMA> {
         });
 
         T::regs().maxrlr().modify(|reg| {
-            // IBI payload data maximum size 1
-            // TODO MCTP specific?
-            reg.set_ibip(0b001.into());
-            // TODO
-            reg.set_mrl(100);
+            reg.set_ibip(config.ibi_payload_size.into());
+            reg.set_mrl(config.max_read_length);
         });
 
         T::regs().maxwlr().modify(|reg| {
-            // TODO
-            reg.set_mwl(100);
+            reg.set_mwl(config.max_write_length);
         });
 
         T::regs().getcapr().modify(|reg| {
-            // Pending read notification
-            // TODO MCTP specific?
-            reg.set_cappend(true);
+            reg.set_cappend(config.pending_read_capable);
         });
 
         if let Some(inst) = config.instance_id {
@@ -187,8 +419,8 @@ impl<'d, T: Instance, TXDMA, RXDMA> I3cTarget<'d, T, TXDMA, RXDMA> {
             });
         }
 
+        arm_ccc_interrupts(T::regs());
         T::regs().ier().modify(|reg| {
-            reg.set_daupdie(true);
             reg.set_errie(true);
         });
 
@@ -222,4 +454,453 @@ impl<'d, T: Instance, TXDMA, RXDMA> I3cTarget<'d, T, TXDMA, RXDMA> {
             reg.set_mtype(0b1000.into())
         });
     }
+
+    /// Raise an In-Band Interrupt carrying `mandatory_byte` plus `payload`.
+    ///
+    /// `payload` must fit within the IBI payload size configured through
+    /// [`TargetConfig`] (`maxrlr().ibip()`), which covers the mandatory byte
+    /// as well.
+    pub async fn request_ibi(&mut self, mandatory_byte: u8, payload: &[u8]) -> Result<(), IbiError> {
+        let regs = T::regs();
+
+        let max_bytes = ibip_to_bytes(regs.maxrlr().read().ibip().to_bits());
+        if 1 + payload.len() > max_bytes as usize {
+            return Err(IbiError::PayloadTooLarge);
+        }
+
+        regs.ibidr().write(|w| w.set_ibidat(mandatory_byte));
+        for &byte in payload {
+            regs.ibidr().write(|w| w.set_ibidat(byte));
+        }
+
+        T::state().error.take();
+        regs.ier().modify(|w| {
+            w.set_ibiie(true);
+            w.set_errie(true);
+        });
+        regs.cr().modify(|reg| {
+            reg.set_mtype(0b0100.into())
+        });
+
+        // A rejected IBI (ser.ibinakf()) is reported through the same
+        // generic error path as any other bus error (see the ISR), so race
+        // the accept path against `wait_for_error` rather than re-reading
+        // `ser` here - that keeps clearing `ser` solely the ISR's job.
+        let accepted = core::future::poll_fn(|cx| {
+            T::state().waker.register(cx.waker());
+            if regs.sr().read().ibif() {
+                regs.sr().write(|w| w.set_ibif(true));
+                core::task::Poll::Ready(())
+            } else {
+                core::task::Poll::Pending
+            }
+        });
+
+        match select(accepted, wait_for_error::<T>()).await {
+            // An unrelated bus error can land in the mailbox in the same
+            // poll window `ibif` is observed set; re-check it rather than
+            // assuming `First` means a clean accept.
+            Either::First(()) => match T::state().error.take() {
+                Some(I3cError::Nack) => Err(IbiError::Rejected),
+                Some(err) => Err(IbiError::Bus(err)),
+                None => Ok(()),
+            },
+            Either::Second(I3cError::Nack) => Err(IbiError::Rejected),
+            Either::Second(err) => Err(IbiError::Bus(err)),
+        }
+    }
+
+    /// Wait for the controller to start a private write and receive its data into `buf`.
+    ///
+    /// Returns the number of bytes actually written by the controller, which may be
+    /// less than `buf.len()`.
+    pub async fn read(&mut self, buf: &mut [u8]) -> Result<usize, I3cError>
+    where
+        RXDMA: crate::i3c::RxDma<T>,
+    {
+        let regs = T::regs();
+
+        T::state().error.take();
+        regs.ier().modify(|w| {
+            w.set_rxfnfie(true);
+            w.set_errie(true);
+        });
+
+        let src = regs.rdr().as_ptr() as *mut u32;
+        let request = self.rx_dma.request();
+        let transfer = unsafe { Transfer::new_read(&mut self.rx_dma, request, src, buf, Default::default()) };
+
+        regs.cr().modify(|w| {
+            w.set_rxdmaen(true);
+        });
+
+        // A controller NACK (or any other bus error) never completes the DMA
+        // transfer, so race it against the error reported by the ISR.
+        // Dropping `transfer` in the `Either::Second` arm stops the DMA
+        // channel instead of leaving it armed forever.
+        match select(transfer, wait_for_error::<T>()).await {
+            Either::First(()) => match T::state().error.take() {
+                // The FIFO can drain (completing the DMA) in the same window
+                // the bus-level error arrives, so still check the mailbox
+                // rather than assuming `First` means a clean transfer.
+                Some(err) => Err(err),
+                None => Ok(buf.len() - regs.rxblr().read().rxblr() as usize),
+            },
+            Either::Second(err) => Err(err),
+        }
+    }
+
+    /// Send `buf` to the controller as a private read response.
+    pub async fn write(&mut self, buf: &[u8]) -> Result<(), I3cError>
+    where
+        TXDMA: crate::i3c::TxDma<T>,
+    {
+        let regs = T::regs();
+
+        T::state().error.take();
+        regs.ier().modify(|w| {
+            w.set_txfnfie(true);
+            w.set_errie(true);
+        });
+
+        let dst = regs.tdr().as_ptr() as *mut u32;
+        let request = self.tx_dma.request();
+        let transfer = unsafe { Transfer::new_write(&mut self.tx_dma, request, buf, dst, Default::default()) };
+
+        regs.cr().modify(|w| {
+            w.set_txdmaen(true);
+        });
+
+        // Same concurrent-completion caveat as `read`: a bus error can land
+        // in the mailbox in the same window the DMA drains.
+        match select(transfer, wait_for_error::<T>()).await {
+            Either::First(()) => match T::state().error.take() {
+                Some(err) => Err(err),
+                None => Ok(()),
+            },
+            Either::Second(err) => Err(err),
+        }
+    }
+
+    /// Wait for the next CCC (Common Command Code) the controller sends and
+    /// return it decoded.
+    ///
+    /// Applications should call this in a loop alongside [`Self::read`] /
+    /// [`Self::write`] to keep their state machine (dynamic address, max
+    /// transfer lengths, HDR mode) in sync with what the controller has set.
+    pub async fn next_event(&mut self) -> I3cTargetEvent {
+        let regs = T::regs();
+
+        loop {
+            // The ISR disables the interrupt-enable bit for whichever of
+            // these sources it last observed firing, so it won't keep
+            // re-triggering on a flag we haven't consumed yet. Re-arm them
+            // all before waiting again.
+            arm_ccc_interrupts(regs);
+
+            let (evr, sr) = core::future::poll_fn(|cx| {
+                T::state().waker.register(cx.waker());
+                let evr = regs.evr().read();
+                let sr = regs.sr().read();
+                let pending = evr.0 != 0 || sr.enecf() || sr.disecf() || sr.hdrmf() || sr.hdrxf();
+                if pending {
+                    core::task::Poll::Ready((evr, sr))
+                } else {
+                    core::task::Poll::Pending
+                }
+            })
+            .await;
+
+            if evr.daupdf() {
+                regs.evr().write(|w| w.set_daupdf(true));
+                let addr = regs.dynaddr().read().dyna();
+                return I3cTargetEvent::DynamicAddressChange(addr);
+            }
+
+            if evr.mwlupdf() {
+                regs.evr().write(|w| w.set_mwlupdf(true));
+                let mwl = regs.maxwlr().read().mwl();
+                return I3cTargetEvent::MaxWriteLength(mwl);
+            }
+
+            if evr.mrlupdf() {
+                regs.evr().write(|w| w.set_mrlupdf(true));
+                let mrl = regs.maxrlr().read().mrl();
+                return I3cTargetEvent::MaxReadLength(mrl);
+            }
+
+            if evr.getaccr() {
+                regs.evr().write(|w| w.set_getaccr(true));
+                return I3cTargetEvent::GetMaxDataSpeed;
+            }
+
+            if evr.hjf() {
+                regs.evr().write(|w| w.set_hjf(true));
+                return I3cTargetEvent::HotJoinAcked;
+            }
+
+            if sr.enecf() {
+                regs.sr().write(|w| w.set_enecf(true));
+                let mask = regs.ener().read().enorst() as u8;
+                return I3cTargetEvent::EventsEnabled(mask);
+            }
+            if sr.disecf() {
+                regs.sr().write(|w| w.set_disecf(true));
+                let mask = regs.dier().read().disval() as u8;
+                return I3cTargetEvent::EventsDisabled(mask);
+            }
+            if sr.hdrmf() {
+                regs.sr().write(|w| w.set_hdrmf(true));
+                return I3cTargetEvent::HdrModeEntered;
+            }
+            if sr.hdrxf() {
+                regs.sr().write(|w| w.set_hdrxf(true));
+                return I3cTargetEvent::HdrModeExited;
+            }
+
+            // Event register had a bit set that we don't decode yet (e.g. a
+            // reserved flag) - loop and wait for the next one.
+        }
+    }
+}
+
+/// Characteristics of a device discovered during dynamic address assignment.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct I3cDeviceInfo {
+    /// 48-bit MIPI Provisioned ID.
+    pub pid: u64,
+    /// Bus Characteristics Register.
+    pub bcr: u8,
+    /// Device Characteristics Register.
+    pub dcr: u8,
+    /// Dynamic address assigned to this device.
+    pub dynamic_address: u8,
+}
+
+/// Last address usable for dynamic assignment: 7'h7E and 7'h7F are reserved.
+const LAST_DYNAMIC_ADDRESS: u8 = 0x7D;
+
+pub struct I3cController<'d, T: Instance, TXDMA = NoDma, RXDMA = NoDma> {
+    _peri: PeripheralRef<'d, T>,
+    tx_dma: PeripheralRef<'d, TXDMA>,
+    #[allow(dead_code)]
+    rx_dma: PeripheralRef<'d, RXDMA>,
+    next_dynamic_address: u8,
+}
+
+#[derive(Default)]
+pub struct ControllerConfig {}
+
+impl<'d, T: Instance, TXDMA, RXDMA> I3cController<'d, T, TXDMA, RXDMA> {
+    pub fn new(
+        peri: impl Peripheral<P = T> + 'd,
+        scl: impl Peripheral<P = impl SclPin<T>> + 'd,
+        sda: impl Peripheral<P = impl SdaPin<T>> + 'd,
+        _irq: impl interrupt::typelevel::Binding<T::Interrupt, InterruptHandler<T>> + 'd,
+        tx_dma: impl Peripheral<P = TXDMA> + 'd,
+        rx_dma: impl Peripheral<P = RXDMA> + 'd,
+        _config: ControllerConfig,
+    ) -> Self {
+        into_ref!(peri, scl, sda, tx_dma, rx_dma);
+
+        T::enable();
+        T::reset();
+
+        scl.set_as_af(scl.af_num(), AFType::OutputOpenDrain);
+        sda.set_as_af(sda.af_num(), AFType::OutputOpenDrain);
+
+        // ref RM0492 35.7.2 Controller initialization
+
+        let aval = (T::frequency() / 1_000_000u32).0 as u8;
+        T::regs().timingr1().write(|reg| {
+            reg.set_aval(aval);
+        });
+
+        T::regs().ier().modify(|reg| {
+            reg.set_errie(true);
+        });
+
+        // cfgr.crinit selects the controller (active master) role
+        T::regs().cfgr().modify(|reg| {
+            reg.set_crinit(true);
+            reg.set_en(true);
+        });
+
+        Self {
+            _peri: peri,
+            tx_dma,
+            rx_dma,
+            // 7'h08 is the first address reserved for dynamic assignment;
+            // 7'h00..7'h07 are reserved/broadcast.
+            next_dynamic_address: 0x08,
+        }
+    }
+
+    /// Broadcast ENTDAA and enumerate every target responding to arbitration,
+    /// assigning each a dynamic address in turn.
+    ///
+    /// `N` bounds how many devices can be recorded; devices beyond that are
+    /// still assigned an address on the bus but are not returned. A bus
+    /// error mid-enumeration also ends the loop early, so the returned list
+    /// may be incomplete relative to what's physically on the bus.
+    pub async fn enter_daa<const N: usize>(&mut self) -> heapless::Vec<I3cDeviceInfo, N> {
+        let mut devices = heapless::Vec::new();
+        let regs = T::regs();
+
+        // select Auto-ENTDAA bus mode, per the same cr().mtype() field hotjoin() pokes
+        regs.cr().modify(|reg| {
+            reg.set_mtype(0b0001.into());
+        });
+
+        loop {
+            // The ISR disables `daie` once `daif` fires (it's level-triggered
+            // and stays set until we write it back below), so re-arm it
+            // before every wait.
+            T::state().error.take();
+            regs.ier().modify(|reg| {
+                reg.set_daie(true);
+                reg.set_errie(true);
+            });
+
+            let daif = core::future::poll_fn(|cx| {
+                T::state().waker.register(cx.waker());
+                if regs.evr().read().daif() {
+                    core::task::Poll::Ready(())
+                } else {
+                    core::task::Poll::Pending
+                }
+            });
+
+            // A bus error mid-ENTDAA (e.g. a target dropping off arbitration)
+            // never sets `daif`, so race it the same way every other wait in
+            // this driver does rather than hanging; stop enumerating and
+            // hand back whatever was assigned so far.
+            let had_error = match select(daif, wait_for_error::<T>()).await {
+                // Also re-check the mailbox on the `daif` path, since a bus
+                // error can land there in the same window `daif` fires.
+                Either::First(()) => T::state().error.take().is_some(),
+                Either::Second(_err) => true,
+            };
+            // `daif` is level-triggered and may be set regardless of which
+            // branch above fired, so always clear it before possibly
+            // breaking out of the loop.
+            regs.evr().write(|w| w.set_daif(true));
+            if had_error {
+                break;
+            }
+
+            if !regs.sr().read().daa() {
+                // no more devices joining arbitration
+                break;
+            }
+
+            if self.next_dynamic_address > LAST_DYNAMIC_ADDRESS {
+                // Out of assignable addresses (7'h7E/7'h7F are reserved) -
+                // stop enumerating rather than hand out an address in the
+                // reserved range.
+                break;
+            }
+
+            let pid_lo = regs.wdr().read().0 as u64;
+            let pid_hi = regs.wdr().read().0 as u64;
+            let pid = (pid_hi << 32) | pid_lo;
+            let bcr = regs.wdr().read().0 as u8;
+            let dcr = regs.wdr().read().0 as u8;
+
+            let dynamic_address = self.next_dynamic_address;
+            self.next_dynamic_address += 1;
+
+            regs.wdr().write(|w| w.0 = dynamic_address as u32);
+
+            let _ = devices.push(I3cDeviceInfo {
+                pid,
+                bcr,
+                dcr,
+                dynamic_address,
+            });
+        }
+
+        devices
+    }
+
+    /// Private write transaction, with `cr().mtype()` (the field
+    /// [`I3cTarget::hotjoin`] also sets) fixed to 0b0000; legacy-I2C
+    /// transfers are not offered.
+    pub async fn write(&mut self, dynamic_address: u8, buf: &[u8]) -> Result<(), I3cError>
+    where
+        TXDMA: crate::i3c::TxDma<T>,
+    {
+        let regs = T::regs();
+
+        T::state().error.take();
+        regs.ier().modify(|w| {
+            w.set_errie(true);
+        });
+
+        regs.cr().modify(|reg| {
+            reg.set_mtype(0b0000.into());
+        });
+        regs.tar0().modify(|reg| {
+            reg.set_addr(dynamic_address.into());
+        });
+
+        let dst = regs.tdr().as_ptr() as *mut u32;
+        let request = self.tx_dma.request();
+        let transfer = unsafe { Transfer::new_write(&mut self.tx_dma, request, buf, dst, Default::default()) };
+
+        regs.cr().modify(|w| {
+            w.set_txdmaen(true);
+        });
+
+        // A NACK from the target never completes the DMA transfer, so race
+        // it against the error the ISR reports (see I3cTarget::write). Also
+        // re-check the mailbox on a clean DMA completion, since a bus error
+        // can land there in the same window the FIFO drains.
+        match select(transfer, wait_for_error::<T>()).await {
+            Either::First(()) => match T::state().error.take() {
+                Some(err) => Err(err),
+                None => Ok(()),
+            },
+            Either::Second(err) => Err(err),
+        }
+    }
+
+    /// Private read transaction, with `cr().mtype()` (the field
+    /// [`I3cTarget::hotjoin`] also sets) fixed to 0b0000; legacy-I2C
+    /// transfers are not offered.
+    pub async fn read(&mut self, dynamic_address: u8, buf: &mut [u8]) -> Result<usize, I3cError>
+    where
+        RXDMA: crate::i3c::RxDma<T>,
+    {
+        let regs = T::regs();
+
+        T::state().error.take();
+        regs.ier().modify(|w| {
+            w.set_errie(true);
+        });
+
+        regs.cr().modify(|reg| {
+            reg.set_mtype(0b0000.into());
+        });
+        regs.tar0().modify(|reg| {
+            reg.set_addr(dynamic_address.into());
+        });
+
+        let src = regs.rdr().as_ptr() as *mut u32;
+        let request = self.rx_dma.request();
+        let transfer = unsafe { Transfer::new_read(&mut self.rx_dma, request, src, buf, Default::default()) };
+
+        regs.cr().modify(|w| {
+            w.set_rxdmaen(true);
+        });
+
+        match select(transfer, wait_for_error::<T>()).await {
+            Either::First(()) => match T::state().error.take() {
+                Some(err) => Err(err),
+                None => Ok(buf.len() - regs.rxblr().read().rxblr() as usize),
+            },
+            Either::Second(err) => Err(err),
+        }
+    }
 }